@@ -1,6 +1,6 @@
 use std::cmp;
-use std::io::Read;
-use std::mem::MaybeUninit;
+use std::io::{BorrowedBuf, Read};
+use std::mem::{self, MaybeUninit};
 
 /// A buffer object for performing buffered reads to the database.
 #[derive(Debug)]
@@ -23,6 +23,16 @@ pub struct Buffer {
     /// reader operations by providing a `BorrowedBuf` the number of initialized and uninitialized
     /// bytes.
     initialized: usize,
+
+    /// The reader's real position corresponding to `self.end`, tracked here so `seek_relative`
+    /// can derive the logical stream position without making a real `stream_position()` call
+    /// (and the syscall that implies) on every seek.
+    ///
+    /// This assumes a `Buffer` is only ever driven by one consistent stream across calls, and
+    /// that the stream started at position `0` — the same assumption `read_some`/`fill_buf`/
+    /// `read_into_end`/`seek_relative` already make by taking `reader` as a per-call argument
+    /// instead of storing it.
+    physical_pos: u64,
 }
 
 impl Buffer {
@@ -32,6 +42,7 @@ impl Buffer {
             pos: 0,
             end: 0,
             initialized: 0,
+            physical_pos: 0,
         }
     }
 
@@ -142,36 +153,41 @@ impl Buffer {
     /// Returns `Ok(0)` according to the specification for `std::io::Read`, where if the reader hits
     /// `EOF`, or this buffer has a length of `0`, then `Ok(0)` is returned.
     pub fn read_some<R: Read>(&mut self, reader: &mut R) -> std::io::Result<usize> {
-        // Create a slice from the cursor position onwards.
-        let slice = &mut self.buf[self.pos..];
-
-        // **SAFETY**: `reader.read()` is always going to insert data into our buffer, even when
-        //             the data at any position is uninitialized. The data after `slice.len()` is
-        //             not a problem, since external sources can only access between `self.pos` and
-        //             `self.filled`, which will always be an initialized slice of unread data.
-        //
-        //             `reader.read` will also never exceed the length of `slice` (as defined within
-        //             the fundamental contract of `std::io::Read`.
-        let bytes = unsafe {
-            let ptr = slice.as_mut_ptr();
-            let buffer = std::slice::from_raw_parts_mut(ptr as *mut u8, slice.len());
+        // Capture this ahead of the borrow below: `buf[self.pos..]` is only fully initialized
+        // once `self.initialized` reaches the end of the whole allocation.
+        let fully_init = self.initialized >= self.buf.len();
+
+        // Borrow the buffer from the cursor position onwards. `BorrowedBuf::set_init` is
+        // all-or-nothing, so it can only be asserted when `buf[self.pos..]` is initialized all
+        // the way to the end of the allocation.
+        let mut borrowed_buf = BorrowedBuf::from(&mut self.buf[self.pos..]);
+
+        // SAFETY: `fully_init` is only true once every byte of this slice already is.
+        unsafe {
+            declare_init(&mut borrowed_buf, fully_init);
+        }
 
-            reader.read(buffer)?
-        };
+        reader.read_buf(borrowed_buf.unfilled())?;
 
+        let bytes = borrowed_buf.len();
         if bytes == 0 {
             return Ok(0);
         }
 
         // The amount of bytes there are available to read is equal to the amount of bytes read from
-        // `reader.read()`.
+        // `reader.read_buf()`.
         self.end += bytes;
+        self.physical_pos += bytes as u64;
 
-        // If `self.pos + bytes` is less than `self.initialized`, then `self.initialized` bytes
-        // remains correct. If `self.pos + bytes` is greater than `self.initialized`, then this
-        // function has initialized more bytes of information, and we need to adjust
-        // `self.initialized` accordingly.
-        self.initialized = cmp::max(self.pos + bytes, self.initialized);
+        // If the reader left the slice fully initialized, that covers the rest of the whole
+        // buffer (not just the bytes it actually wrote); otherwise only the bytes it wrote are
+        // guaranteed initialized.
+        let now_initialized = if borrowed_buf.is_init() {
+            self.buf.len()
+        } else {
+            self.pos + bytes
+        };
+        self.initialized = cmp::max(now_initialized, self.initialized);
 
         Ok(bytes)
     }
@@ -182,23 +198,297 @@ impl Buffer {
     pub fn fill_buf<R: Read>(&mut self, reader: &mut R) -> std::io::Result<usize> {
         self.pos = 0;
 
-        let bytes = unsafe {
-            let ptr = self.buf.as_mut_ptr();
-            let buffer = std::slice::from_raw_parts_mut(ptr as *mut u8, self.len());
+        let fully_init = self.initialized >= self.buf.len();
+        let mut borrowed_buf = BorrowedBuf::from(&mut self.buf[..]);
 
-            reader.read(buffer)?
-        };
+        // SAFETY: `fully_init` is only true once the whole buffer, which is what this slice
+        // covers, has already been initialized by previous reads.
+        unsafe {
+            declare_init(&mut borrowed_buf, fully_init);
+        }
+
+        reader.read_buf(borrowed_buf.unfilled())?;
 
+        let bytes = borrowed_buf.len();
         if bytes == 0 {
             return Ok(0);
         }
 
         self.end = bytes;
+        self.physical_pos += bytes as u64;
 
-        // Calculate `self.initialized`, setting it to either `self.pos + bytes` or
-        // `self.initialized`, depending on which is greater.
-        self.initialized = cmp::max(self.pos + bytes, self.initialized);
+        let now_initialized = if borrowed_buf.is_init() { self.buf.len() } else { bytes };
+        self.initialized = cmp::max(now_initialized, self.initialized);
 
         Ok(bytes)
     }
+
+    /// Returns a slice of at least `amount` unread bytes, pulling more data from `reader` as
+    /// needed, without consuming any of it. Fewer than `amount` bytes are returned only once
+    /// `reader` reaches EOF.
+    ///
+    /// This never consumes any bytes: the returned slice starts at the same unread byte that
+    /// `buffer()` started at before the call, although compacting the buffer to make room may
+    /// shift that byte down so `self.pos()` becomes `0`. Callers peek, decide, then consume via
+    /// `reposition`.
+    pub fn peek<R: Read>(&mut self, reader: &mut R, amount: usize) -> std::io::Result<&[u8]> {
+        self.fill_to(reader, amount)?;
+
+        Ok(self.buffer())
+    }
+
+    /// Identical to `peek`, but returns an `UnexpectedEof` error if fewer than `amount` bytes
+    /// are available once `reader` reaches EOF.
+    pub fn ensure<R: Read>(&mut self, reader: &mut R, amount: usize) -> std::io::Result<&[u8]> {
+        self.fill_to(reader, amount)?;
+
+        if self.buffer().len() < amount {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "not enough bytes available to satisfy `ensure`",
+            ));
+        }
+
+        Ok(self.buffer())
+    }
+
+    /// Reads from `reader` into `out` until `delim` is found or `reader` reaches EOF, mirroring
+    /// `BufRead::read_until` semantics. `out` receives everything up to and including the
+    /// delimiter, and the cursor is advanced past it.
+    ///
+    /// Returns the total number of bytes appended to `out`. Returns `Ok(0)` only at EOF with
+    /// nothing buffered.
+    pub fn read_until<R: Read>(
+        &mut self,
+        reader: &mut R,
+        delim: u8,
+        out: &mut Vec<u8>,
+    ) -> std::io::Result<usize> {
+        let mut total = 0;
+
+        loop {
+            let buffer = self.buffer();
+
+            if let Some(i) = find_byte(delim, buffer) {
+                out.extend_from_slice(&buffer[..=i]);
+                total += i + 1;
+                self.reposition(i + 1);
+
+                return Ok(total);
+            }
+
+            let unread = buffer.len();
+            out.extend_from_slice(buffer);
+            total += unread;
+            self.reposition(unread);
+
+            // The buffer is now fully drained (`pos == end`); discard it so `read_some` refills
+            // from offset `0` instead of `self.buf[self.pos..]`, which would shrink to nothing
+            // once `pos` reaches capacity and make every further read look like EOF.
+            self.discard_buffer();
+
+            if self.read_some(reader)? == 0 {
+                return Ok(total);
+            }
+        }
+    }
+
+    /// Seeks `reader` by `offset` bytes relative to the current logical stream position,
+    /// reusing buffered data instead of touching `reader` when the target lands inside it.
+    ///
+    /// This mirrors `BufReader::seek_relative`: small backward/forward seeks during frame
+    /// parsing only adjust `self.pos()`, without making any call on `reader` at all, while a
+    /// target outside the buffered window falls back to a real `Seek::seek` on `reader` and
+    /// discards the buffer. Returns the resulting logical stream position.
+    pub fn seek_relative<R: std::io::Seek>(
+        &mut self,
+        reader: &mut R,
+        offset: i64,
+    ) -> std::io::Result<u64> {
+        let pos = self.pos as u64;
+
+        if offset < 0 {
+            if pos.checked_sub((-offset) as u64).is_some() {
+                self.unposition((-offset) as usize);
+                return Ok(self.logical_position());
+            }
+        } else if let Some(new_pos) = pos.checked_add(offset as u64) {
+            if new_pos <= self.end as u64 {
+                self.reposition(offset as usize);
+                return Ok(self.logical_position());
+            }
+        }
+
+        // `reader`'s physical cursor already sits `self.end - self.pos` bytes ahead of our
+        // logical position (the unread bytes still resident in the buffer we're about to
+        // discard), so that remainder has to come back out of the seek distance — mirroring
+        // how `std::io::BufReader`'s `Seek::seek` corrects for the same thing.
+        let remainder = (self.end - self.pos) as i64;
+        self.discard_buffer();
+
+        self.physical_pos = reader.seek(std::io::SeekFrom::Current(offset - remainder))?;
+
+        Ok(self.physical_pos)
+    }
+
+    /// Derives the logical stream position from `self.physical_pos` and the unread bytes still
+    /// resident in the buffer, which `reader` has already physically advanced past but which
+    /// haven't been logically consumed yet.
+    fn logical_position(&self) -> u64 {
+        self.physical_pos - (self.end - self.pos) as u64
+    }
+
+    /// Makes sure at least `amount` unread bytes are resident in the buffer: compacting the
+    /// unread region down to offset `0` if it doesn't already fit, growing the underlying
+    /// allocation if it's still too small, then pulling from `reader` until `amount` bytes are
+    /// resident or `reader` hits EOF.
+    fn fill_to<R: Read>(&mut self, reader: &mut R, amount: usize) -> std::io::Result<()> {
+        if self.end - self.pos < amount {
+            self.compact();
+
+            if self.buf.len() < amount {
+                self.grow(amount);
+            }
+
+            while self.end < amount {
+                if self.read_into_end(reader)? == 0 {
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Shifts the unread region `buf[pos..end]` down to offset `0`, discarding already-consumed
+    /// bytes so their space can be reused.
+    fn compact(&mut self) {
+        let unread = self.end - self.pos;
+        self.buf.copy_within(self.pos..self.end, 0);
+
+        // Anything initialized past `pos` is now initialized starting at `0` instead; clamp
+        // into the new, shorter frame of reference.
+        self.initialized = cmp::max(self.initialized.saturating_sub(self.pos), unread);
+        self.pos = 0;
+        self.end = unread;
+    }
+
+    /// Reallocates the underlying buffer to hold at least `amount` bytes, doubling the current
+    /// capacity instead if that is already larger, and preserving the unread bytes at the front.
+    fn grow(&mut self, amount: usize) {
+        let new_capacity = cmp::max(amount, self.buf.len() * 2);
+        let mut new_buf = Box::new_uninit_slice(new_capacity);
+        new_buf[..self.end].copy_from_slice(&self.buf[..self.end]);
+
+        self.buf = new_buf;
+
+        // Only `buf[..self.end]` was copied into the new, otherwise-uninitialized allocation;
+        // clamp `self.initialized` so a stale, too-large value (e.g. left over from
+        // `discard_buffer`) doesn't claim fresh heap garbage as initialized.
+        self.initialized = cmp::min(self.initialized, self.end);
+    }
+
+    /// Reads more data into `buf[end..]`, leaving the unread region `buf[pos..end]` untouched,
+    /// and advances `end`/`initialized` accordingly. Returns `Ok(0)` at EOF.
+    fn read_into_end<R: Read>(&mut self, reader: &mut R) -> std::io::Result<usize> {
+        let base = self.end;
+        let fully_init = self.initialized >= self.buf.len();
+        let mut borrowed_buf = BorrowedBuf::from(&mut self.buf[base..]);
+
+        // SAFETY: identical reasoning to `read_some`, except the target is `buf[end..]` so the
+        // already-buffered, unread bytes at `buf[pos..end]` are left untouched.
+        unsafe {
+            declare_init(&mut borrowed_buf, fully_init);
+        }
+
+        reader.read_buf(borrowed_buf.unfilled())?;
+
+        let bytes = borrowed_buf.len();
+        if bytes == 0 {
+            return Ok(0);
+        }
+
+        self.end = base + bytes;
+        self.physical_pos += bytes as u64;
+
+        let now_initialized = if borrowed_buf.is_init() { self.buf.len() } else { base + bytes };
+        self.initialized = cmp::max(now_initialized, self.initialized);
+
+        Ok(bytes)
+    }
+}
+
+impl std::io::Write for Buffer {
+    /// Appends `buf` into `self.buf[self.end..]`, growing the underlying allocation if there
+    /// isn't enough room, and advances `self.end` past the newly written bytes.
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.buf.len() - self.end < buf.len() {
+            self.grow(self.end + buf.len());
+        }
+
+        for (slot, &byte) in self.buf[self.end..self.end + buf.len()].iter_mut().zip(buf) {
+            slot.write(byte);
+        }
+
+        self.end += buf.len();
+        self.initialized = cmp::max(self.end, self.initialized);
+
+        Ok(buf.len())
+    }
+
+    /// No-op: `Buffer` is an in-memory buffer with nothing further to flush on its own. Callers
+    /// that need writes coalesced and flushed to a transport should use
+    /// `BufferedConnectorWriter`.
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Declares `borrowed_buf` initialized, if `already_init` says every byte backing it is.
+///
+/// `BorrowedBuf::set_init` is all-or-nothing: it has no way to assert that only a prefix of the
+/// slice is initialized. So unlike a `self.initialized - base` byte count, this can only be
+/// asserted once the *entire* slice is already known to be initialized; a partially-initialized
+/// slice is left for `reader.read_buf` to zero-fill the remainder of itself.
+///
+/// # Safety
+///
+/// `already_init` must be `true` only if every byte backing `borrowed_buf` is initialized.
+unsafe fn declare_init(borrowed_buf: &mut BorrowedBuf<'_>, already_init: bool) {
+    if already_init {
+        unsafe {
+            borrowed_buf.set_init();
+        }
+    }
+}
+
+/// Finds the first occurrence of `needle` in `haystack`, scanning a word (`usize`) at a time
+/// once the remaining slice is long enough, falling back to a byte-by-byte scan for the tail.
+///
+/// This is the same SWAR ("SIMD within a register") technique `memchr` implementations use:
+/// XOR each word against one filled with copies of `needle`, which turns matching bytes to
+/// zero, then test for a zero byte across the whole word at once.
+fn find_byte(needle: u8, haystack: &[u8]) -> Option<usize> {
+    const WORD_SIZE: usize = mem::size_of::<usize>();
+    const LO: usize = usize::from_ne_bytes([0x01; WORD_SIZE]);
+    const HI: usize = usize::from_ne_bytes([0x80; WORD_SIZE]);
+
+    fn contains_zero_byte(x: usize) -> bool {
+        x.wrapping_sub(LO) & !x & HI != 0
+    }
+
+    let repeated_needle = usize::from_ne_bytes([needle; WORD_SIZE]);
+
+    let mut i = 0;
+    while i + WORD_SIZE <= haystack.len() {
+        let word = usize::from_ne_bytes(haystack[i..i + WORD_SIZE].try_into().unwrap());
+
+        if contains_zero_byte(word ^ repeated_needle) {
+            break;
+        }
+
+        i += WORD_SIZE;
+    }
+
+    haystack[i..].iter().position(|&b| b == needle).map(|pos| i + pos)
 }
\ No newline at end of file
@@ -0,0 +1,196 @@
+//! Streaming I/O adapters over `Connector` implementations.
+//!
+//! These adapters let callers layer `std::io`-based parsers (anything that consumes
+//! `Read`/`BufRead`) directly on top of a `Connector`, without every connector having to
+//! reimplement buffering itself.
+
+use std::io::{self, BufRead, Read, Write};
+
+use crate::connector::error::ConnectorError;
+use crate::connector::Connector;
+use crate::internal::buffer::Buffer;
+
+/// A `BufRead`-compatible reader that wraps a `Connector` and reuses a `Buffer` to avoid
+/// pulling from the connector on every read.
+///
+/// This mirrors `std::io::BufReader`: it performs large, infrequent reads over an expensive
+/// source (the connector) and serves small reads out of an in-memory buffer, so `read_line`,
+/// `read_until`, or any other `BufRead`-consuming parser can be layered over a transport
+/// without rewriting buffering logic for every connector.
+pub(crate) struct BufferedConnectorReader<C: Connector> {
+    connector: C,
+    buffer: Buffer,
+}
+
+impl<C: Connector> BufferedConnectorReader<C> {
+    /// Wraps `connector` in a `BufferedConnectorReader` backed by a `Buffer` of `capacity`
+    /// bytes.
+    pub(crate) fn new(connector: C, capacity: usize) -> Self {
+        BufferedConnectorReader {
+            connector,
+            buffer: Buffer::new(capacity),
+        }
+    }
+
+    /// Returns a reference to the underlying connector.
+    pub(crate) fn get_ref(&self) -> &C {
+        &self.connector
+    }
+
+    /// Returns a mutable reference to the underlying connector.
+    ///
+    /// Reading from or writing to the connector directly through this reference can
+    /// desynchronize it from the data already resident in this reader's buffer.
+    pub(crate) fn get_mut(&mut self) -> &mut C {
+        &mut self.connector
+    }
+
+    /// Consumes this `BufferedConnectorReader`, returning the underlying connector.
+    pub(crate) fn into_inner(self) -> C {
+        self.connector
+    }
+}
+
+impl<C: Connector> Read for BufferedConnectorReader<C> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // If the buffer is empty and the requested read is at least as large as the buffer
+        // itself, bypass it entirely and pull straight into `buf`, same as `BufReader::read`.
+        if self.buffer.pos() == self.buffer.end() && buf.len() >= self.buffer.len() {
+            self.buffer.discard_buffer();
+            return pull(&self.connector, buf);
+        }
+
+        let nread = {
+            let mut rem = self.fill_buf()?;
+            rem.read(buf)?
+        };
+
+        self.consume(nread);
+        Ok(nread)
+    }
+}
+
+impl<C: Connector> BufRead for BufferedConnectorReader<C> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.buffer.pos() >= self.buffer.end() {
+            self.buffer.discard_buffer();
+            self.buffer.read_some(&mut ConnectorSource(&self.connector))?;
+        }
+
+        Ok(self.buffer.buffer())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.buffer.reposition(amt);
+    }
+}
+
+/// A buffered writer that coalesces small writes into a `Buffer` and flushes them to a
+/// `Connector` in one or a few `push` calls.
+///
+/// This mirrors `std::io::BufWriter`: codec output made up of many small writes (e.g. a field
+/// at a time from `rmp::encode`) is combined into large, infrequent transport writes instead of
+/// one `push` per field. The buffer is flushed when it fills up, on an explicit `flush`, and on
+/// drop.
+pub(crate) struct BufferedConnectorWriter<C: Connector> {
+    connector: C,
+    buffer: Buffer,
+}
+
+impl<C: Connector> BufferedConnectorWriter<C> {
+    /// Wraps `connector` in a `BufferedConnectorWriter` backed by a `Buffer` of `capacity`
+    /// bytes.
+    pub(crate) fn new(connector: C, capacity: usize) -> Self {
+        BufferedConnectorWriter {
+            connector,
+            buffer: Buffer::new(capacity),
+        }
+    }
+
+    /// Returns a reference to the underlying connector.
+    pub(crate) fn get_ref(&self) -> &C {
+        &self.connector
+    }
+
+    /// Returns a mutable reference to the underlying connector.
+    ///
+    /// Writing to the connector directly through this reference can desynchronize it from the
+    /// data already buffered by this writer.
+    pub(crate) fn get_mut(&mut self) -> &mut C {
+        &mut self.connector
+    }
+}
+
+impl<C: Connector> Write for BufferedConnectorWriter<C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.buffer.len() - self.buffer.end() < buf.len() {
+            self.flush()?;
+        }
+
+        // A write at least as large as the whole buffer would fill it completely even after the
+        // flush above, so bypass the buffer and push it straight through, same as
+        // `BufWriter::write`.
+        if buf.len() >= self.buffer.len() {
+            return push(&self.connector, buf);
+        }
+
+        self.buffer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // `Connector::push` is allowed to push short, same as `std::io::Write::write`, so one
+        // call isn't enough to guarantee the buffer is drained: keep pushing the remainder until
+        // it is, same as `BufWriter::flush`.
+        while !self.buffer.buffer().is_empty() {
+            let unflushed = self.buffer.buffer();
+            let written = push(&self.connector, unflushed)?;
+
+            if written == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write the buffered data to the connector",
+                ));
+            }
+
+            self.buffer.reposition(written);
+        }
+
+        self.buffer.discard_buffer();
+
+        Ok(())
+    }
+}
+
+impl<C: Connector> Drop for BufferedConnectorWriter<C> {
+    fn drop(&mut self) {
+        // Best-effort, same as `BufWriter`: a dropped writer has no way to surface a flush
+        // error to its caller.
+        let _ = self.flush();
+    }
+}
+
+/// Adapts a `&Connector` into a `std::io::Read` source, so `Buffer`'s reader-based methods
+/// can be reused instead of calling `Connector::pull` directly.
+struct ConnectorSource<'a, C: Connector>(&'a C);
+
+impl<C: Connector> Read for ConnectorSource<'_, C> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.pull(buf).map_err(to_io_error)
+    }
+}
+
+/// Pulls directly from `connector` into `buf`, translating `ConnectorError` into `io::Error`.
+fn pull<C: Connector>(connector: &C, buf: &mut [u8]) -> io::Result<usize> {
+    connector.pull(buf).map_err(to_io_error)
+}
+
+/// Pushes `buf` directly to `connector`, translating `ConnectorError` into `io::Error`.
+fn push<C: Connector>(connector: &C, buf: &[u8]) -> io::Result<usize> {
+    connector.push(buf).map_err(to_io_error)
+}
+
+/// Translates a `ConnectorError` into an `io::Error` so connector-backed adapters can
+/// participate in `std::io` traits.
+fn to_io_error(err: ConnectorError) -> io::Error {
+    io::Error::other(err)
+}
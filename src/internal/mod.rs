@@ -0,0 +1,5 @@
+//! Internal module. Contains implementation details that back the crate's public API but
+//! are not exposed directly to consumers of the crate.
+
+pub(crate) mod buffer;
+pub(crate) mod io;
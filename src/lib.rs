@@ -1,11 +1,14 @@
 #![feature(maybe_uninit_slice)]
 #![feature(read_buf)]
+#![feature(core_io_borrowed_buf)]
+#![feature(borrowed_buf_init)]
 #![feature(string_from_utf8_lossy_owned)]
 
 #[cfg(test)]
 mod tests;
 
 pub(in crate) mod serialization;
+pub mod connector;
 mod internal;
 
 pub use crate::serialization::encode as encode;
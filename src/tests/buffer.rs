@@ -1,5 +1,5 @@
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{BufReader, Cursor, Read};
 use crate::internal::buffer::Buffer;
 
 #[test]
@@ -61,4 +61,67 @@ fn write_buffer() {
 
     let result = rmp::decode::read_u8(&mut buf.buffer()).unwrap();
     dbg!(result);
+}
+
+#[test]
+fn peek_grows_buffer_after_discard() {
+    let mut buf = Buffer::new(4);
+    let mut cursor = Cursor::new(b"0123456789".to_vec());
+
+    buf.read_some(&mut cursor).unwrap();
+    buf.discard_buffer();
+
+    let peeked = buf.peek(&mut cursor, 6).unwrap();
+    assert_eq!(peeked, b"456789");
+}
+
+#[test]
+fn read_until_spans_multiple_fills() {
+    let mut buf = Buffer::new(4);
+    let mut cursor = Cursor::new(b"abcdefghij\nxyz".to_vec());
+    let mut out = Vec::new();
+
+    let n = buf.read_until(&mut cursor, b'\n', &mut out).unwrap();
+
+    assert_eq!(n, 11);
+    assert_eq!(out, b"abcdefghij\n");
+}
+
+#[test]
+fn seek_relative_reuses_buffered_data() {
+    let data: Vec<u8> = (0u8..100).collect();
+    let mut cursor = Cursor::new(data.clone());
+    let mut buf = Buffer::new(16);
+
+    // Buffers bytes `0..16`; the cursor's physical position is now `16`.
+    buf.read_some(&mut cursor).unwrap();
+
+    // Forward within the buffered window: must not touch `cursor` at all.
+    let pos = buf.seek_relative(&mut cursor, 5).unwrap();
+    assert_eq!(pos, 5);
+    assert_eq!(cursor.position(), 16);
+    assert_eq!(buf.buffer(), &data[5..16]);
+
+    // Backward, still within the window: same thing.
+    let pos = buf.seek_relative(&mut cursor, -3).unwrap();
+    assert_eq!(pos, 2);
+    assert_eq!(cursor.position(), 16);
+    assert_eq!(buf.buffer(), &data[2..16]);
+}
+
+#[test]
+fn seek_relative_falls_back_outside_window() {
+    let data: Vec<u8> = (0u8..100).collect();
+    let mut cursor = Cursor::new(data);
+    let mut buf = Buffer::new(8);
+
+    // Buffers bytes `0..8`; the cursor's physical position is now `8`, eight bytes ahead of
+    // our logical position of `0`.
+    buf.read_some(&mut cursor).unwrap();
+
+    // This lands outside the buffered window, so it must fall back to a real seek.
+    let pos = buf.seek_relative(&mut cursor, 20).unwrap();
+
+    assert_eq!(pos, 20);
+    assert_eq!(cursor.position(), 20);
 }
\ No newline at end of file
@@ -0,0 +1,192 @@
+use std::cell::{Cell, RefCell};
+use std::cmp;
+use std::collections::VecDeque;
+use std::io::{BufRead, Read, Write};
+use std::rc::Rc;
+
+use crate::connector::error::ConnectorError;
+use crate::connector::Connector;
+use crate::internal::io::{BufferedConnectorReader, BufferedConnectorWriter};
+
+/// An in-memory `Connector` double, for exercising the `io` adapters without a real transport.
+struct FakeConnector {
+    connected: bool,
+    read_data: RefCell<VecDeque<u8>>,
+    pull_calls: Rc<Cell<usize>>,
+    written: Rc<RefCell<Vec<u8>>>,
+    push_calls: Rc<Cell<usize>>,
+    push_limit: Option<usize>,
+}
+
+impl FakeConnector {
+    fn with_data(data: &[u8]) -> Self {
+        FakeConnector {
+            connected: false,
+            read_data: RefCell::new(data.iter().copied().collect()),
+            pull_calls: Rc::new(Cell::new(0)),
+            written: Rc::new(RefCell::new(Vec::new())),
+            push_calls: Rc::new(Cell::new(0)),
+            push_limit: None,
+        }
+    }
+
+    fn sink() -> Self {
+        Self::with_data(&[])
+    }
+
+    fn with_push_limit(mut self, limit: usize) -> Self {
+        self.push_limit = Some(limit);
+        self
+    }
+
+    fn pull_calls_handle(&self) -> Rc<Cell<usize>> {
+        self.pull_calls.clone()
+    }
+
+    fn push_calls_handle(&self) -> Rc<Cell<usize>> {
+        self.push_calls.clone()
+    }
+
+    fn written_handle(&self) -> Rc<RefCell<Vec<u8>>> {
+        self.written.clone()
+    }
+}
+
+impl Connector for FakeConnector {
+    type Options = ();
+    type Connection = ();
+
+    fn connect(&self, _options: ()) -> Result<(), ConnectorError> {
+        Ok(())
+    }
+
+    fn connected(&mut self) -> &mut bool {
+        &mut self.connected
+    }
+
+    fn push(&self, bytes: &[u8]) -> Result<usize, ConnectorError> {
+        self.push_calls.set(self.push_calls.get() + 1);
+
+        let n = self.push_limit.map_or(bytes.len(), |limit| cmp::min(limit, bytes.len()));
+        self.written.borrow_mut().extend_from_slice(&bytes[..n]);
+
+        Ok(n)
+    }
+
+    fn pull(&self, buffer: &mut [u8]) -> Result<usize, ConnectorError> {
+        self.pull_calls.set(self.pull_calls.get() + 1);
+
+        let mut data = self.read_data.borrow_mut();
+        let n = cmp::min(buffer.len(), data.len());
+
+        for slot in &mut buffer[..n] {
+            *slot = data.pop_front().unwrap();
+        }
+
+        Ok(n)
+    }
+}
+
+#[test]
+fn read_bypasses_buffer_for_large_reads() {
+    let data: Vec<u8> = (0u8..64).collect();
+    let connector = FakeConnector::with_data(&data);
+    let mut reader = BufferedConnectorReader::new(connector, 8);
+
+    let mut out = vec![0u8; 32];
+    let n = reader.read(&mut out).unwrap();
+
+    assert_eq!(n, 32);
+    assert_eq!(out, data[..32]);
+}
+
+#[test]
+fn read_buffers_small_reads() {
+    let data: Vec<u8> = (0u8..16).collect();
+    let connector = FakeConnector::with_data(&data);
+    let pull_calls = connector.pull_calls_handle();
+    let mut reader = BufferedConnectorReader::new(connector, 8);
+
+    let mut collected = Vec::new();
+    let mut chunk = [0u8; 4];
+
+    for _ in 0..4 {
+        let n = reader.read(&mut chunk).unwrap();
+        assert_eq!(n, 4);
+        collected.extend_from_slice(&chunk);
+    }
+
+    assert_eq!(collected, data);
+    // Four 4-byte reads were served out of two 8-byte pulls, proving the buffer is reused
+    // instead of pulling from the connector on every read.
+    assert_eq!(pull_calls.get(), 2);
+}
+
+#[test]
+fn read_reports_eof() {
+    let connector = FakeConnector::with_data(b"hi");
+    let mut reader = BufferedConnectorReader::new(connector, 8);
+
+    let mut out = [0u8; 8];
+    let n = reader.read(&mut out).unwrap();
+    assert_eq!(n, 2);
+
+    let n = reader.read(&mut out).unwrap();
+    assert_eq!(n, 0);
+}
+
+#[test]
+fn buf_read_until_delimiter() {
+    let connector = FakeConnector::with_data(b"hello\nworld");
+    let mut reader = BufferedConnectorReader::new(connector, 4);
+
+    let mut line = Vec::new();
+    let n = BufRead::read_until(&mut reader, b'\n', &mut line).unwrap();
+
+    assert_eq!(n, 6);
+    assert_eq!(line, b"hello\n");
+}
+
+#[test]
+fn write_coalesces_small_writes() {
+    let connector = FakeConnector::sink();
+    let written = connector.written_handle();
+    let push_calls = connector.push_calls_handle();
+
+    {
+        let mut writer = BufferedConnectorWriter::new(connector, 16);
+        writer.write_all(b"abc").unwrap();
+        writer.write_all(b"def").unwrap();
+        writer.flush().unwrap();
+    }
+
+    assert_eq!(*written.borrow(), b"abcdef");
+    // Both writes landed in one `push`, proving they were coalesced instead of going straight
+    // to the connector.
+    assert_eq!(push_calls.get(), 1);
+}
+
+#[test]
+fn flush_drains_short_pushes() {
+    let connector = FakeConnector::sink().with_push_limit(3);
+    let written = connector.written_handle();
+
+    let mut writer = BufferedConnectorWriter::new(connector, 32);
+    writer.write_all(b"0123456789").unwrap();
+    writer.flush().unwrap();
+
+    assert_eq!(*written.borrow(), b"0123456789");
+}
+
+#[test]
+fn drop_flushes_buffer() {
+    let connector = FakeConnector::sink();
+    let written = connector.written_handle();
+
+    {
+        let mut writer = BufferedConnectorWriter::new(connector, 16);
+        writer.write_all(b"flushed on drop").unwrap();
+    }
+
+    assert_eq!(*written.borrow(), b"flushed on drop");
+}